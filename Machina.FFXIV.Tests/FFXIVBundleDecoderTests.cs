@@ -125,6 +125,21 @@ public void FFXIVBundle_StoreData_SplitBundle()
             Assert.AreEqual(101, sut.Messages.Count);
             Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
         }
+        [TestMethod]
+        public void FFXIVBundle_StoreData_BundleTooLongPlusOneBundle()
+        {
+            // changed length from '5C000000' to '00000010'
+            byte[] data = ConversionUtility.HexStringToByteArray("5252A041FF5D46E27F2A644D7B99C475047C4E3F5F01000000000010000001000101000000000000789C33606060D8BF405A40E487AA0033C3FE0A1106574606065586B5E1AF2381520CDDDC20F205F311CE33D3818C070009600B8E");
+            byte[] data2 = ConversionUtility.HexStringToByteArray("5252A041FF5D46E27F2A644D7B99C475047C4E3F5F0100005C000000000001000101000000000000789C33606060D8BF405A40E487AA0033C3FE0A1106574606065586B5E1AF2381520CDDDC20F205F311CE33D3818C070009600B8E");
+
+            FFXIVBundleDecoder sut = new();
+            sut.StoreData(data);
+            sut.StoreData(data2);
+
+            Assert.AreEqual(1, sut.Messages.Count);
+            Assert.AreEqual(1, TestInfrastructure.Listener.Messages.Count);
+        }
+
         [TestMethod]
         public void FFXIVBundle_StoreData_BadMagicPlusOneBundle()
         {
@@ -0,0 +1,174 @@
+﻿// Copyright © 2021 Ravahn - All Rights Reserved
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY. without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see<http://www.gnu.org/licenses/>.
+
+using System;
+using Machina.FFXIV.Headers;
+using Machina.FFXIV.Tests.Utility;
+using Microsoft.VisualStudio.TestTools.UnitTesting;
+
+namespace Machina.FFXIV.Tests
+{
+    [TestClass]
+    public class FFXIVKeepAliveTrackerTests
+    {
+        [TestCleanup]
+        public void TestCleanup()
+        {
+            TestInfrastructure.Listener.Messages.Clear();
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_RequestAndResponse()
+        {
+            FFXIVKeepAliveTracker sut = new();
+
+            Assert.IsTrue(sut.ProcessSentMessage(1000, ConstructKeepAlive(SegmentType.KeepAlive, 5)));
+            Assert.IsTrue(sut.ProcessReceivedMessage(1042, ConstructKeepAlive(SegmentType.KeepAliveResponse, 5), out long roundTripTime));
+
+            Assert.AreEqual(42, roundTripTime);
+            Assert.AreEqual(42, sut.LastRoundTripTime);
+            Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_UnknownResponse()
+        {
+            FFXIVKeepAliveTracker sut = new();
+
+            Assert.IsTrue(sut.ProcessSentMessage(1000, ConstructKeepAlive(SegmentType.KeepAlive, 5)));
+            Assert.IsFalse(sut.ProcessReceivedMessage(1042, ConstructKeepAlive(SegmentType.KeepAliveResponse, 6), out long roundTripTime));
+
+            Assert.AreEqual(-1, roundTripTime);
+            Assert.AreEqual(-1, sut.LastRoundTripTime);
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_IgnoresOtherSegments()
+        {
+            FFXIVKeepAliveTracker sut = new();
+
+            Assert.IsFalse(sut.ProcessSentMessage(1000, ConstructKeepAlive(SegmentType.IPC, 5)));
+            Assert.IsFalse(sut.ProcessSentMessage(1000, new byte[] { 1, 2, 3 }));
+            Assert.IsFalse(sut.ProcessReceivedMessage(1042, ConstructKeepAlive(SegmentType.KeepAlive, 5), out _));
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_MaxPendingRequests()
+        {
+            FFXIVKeepAliveTracker sut = new()
+            {
+                MaxPendingRequests = 2
+            };
+
+            _ = sut.ProcessSentMessage(1000, ConstructKeepAlive(SegmentType.KeepAlive, 1));
+            _ = sut.ProcessSentMessage(2000, ConstructKeepAlive(SegmentType.KeepAlive, 2));
+            _ = sut.ProcessSentMessage(3000, ConstructKeepAlive(SegmentType.KeepAlive, 3));
+
+            Assert.IsFalse(sut.ProcessReceivedMessage(3010, ConstructKeepAlive(SegmentType.KeepAliveResponse, 1), out _));
+            Assert.IsTrue(sut.ProcessReceivedMessage(3020, ConstructKeepAlive(SegmentType.KeepAliveResponse, 2), out long roundTripTime));
+            Assert.AreEqual(1020, roundTripTime);
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_ClockOffset()
+        {
+            FFXIVKeepAliveTracker sut = new();
+
+            Assert.IsNull(sut.ClockOffset);
+
+            _ = sut.ProcessSentMessage(1000, ConstructKeepAlive(SegmentType.KeepAlive, 5));
+            _ = sut.ProcessReceivedMessage(1040, ConstructKeepAlive(SegmentType.KeepAliveResponse, 5), out _);
+
+            // server clock is 500ms ahead, bundle was sent 20ms before it was processed
+            sut.ProcessReceivedEpoch(2000, 2480);
+
+            Assert.AreEqual(500, sut.ClockOffset);
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_ClockOffset_SamplesBeforeRoundTripTime()
+        {
+            FFXIVKeepAliveTracker sut = new();
+
+            _ = sut.ProcessSentMessage(1000, ConstructKeepAlive(SegmentType.KeepAlive, 5));
+
+            // no round-trip time is known yet, so the sample is uncorrected
+            sut.ProcessReceivedEpoch(1020, 1500);
+            Assert.AreEqual(480, sut.ClockOffset);
+
+            // the first round-trip time also corrects the earlier sample
+            _ = sut.ProcessReceivedMessage(1040, ConstructKeepAlive(SegmentType.KeepAliveResponse, 5), out _);
+            Assert.AreEqual(500, sut.ClockOffset);
+
+            // a later, more delayed sample does not replace it
+            sut.ProcessReceivedEpoch(2030, 2500);
+            Assert.AreEqual(500, sut.ClockOffset);
+
+            // a later round-trip time applies to every sample
+            _ = sut.ProcessSentMessage(3000, ConstructKeepAlive(SegmentType.KeepAlive, 6));
+            _ = sut.ProcessReceivedMessage(3060, ConstructKeepAlive(SegmentType.KeepAliveResponse, 6), out _);
+            Assert.AreEqual(510, sut.ClockOffset);
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_ClockOffset_UsesLeastDelayedSample()
+        {
+            FFXIVKeepAliveTracker sut = new()
+            {
+                MaxClockOffsetSamples = 2
+            };
+
+            sut.ProcessReceivedEpoch(1000, 1500);
+            sut.ProcessReceivedEpoch(2050, 2500);
+            Assert.AreEqual(500, sut.ClockOffset);
+
+            // ignores bundles without a server timestamp
+            sut.ProcessReceivedEpoch(3000, 0);
+            Assert.AreEqual(500, sut.ClockOffset);
+
+            // oldest sample is discarded once the limit is reached
+            sut.ProcessReceivedEpoch(3030, 3500);
+            Assert.AreEqual(470, sut.ClockOffset);
+        }
+
+        [TestMethod]
+        public void FFXIVKeepAliveTracker_ClockOffset_OneSamplePerBundle()
+        {
+            FFXIVKeepAliveTracker sut = new()
+            {
+                MaxClockOffsetSamples = 2
+            };
+
+            sut.ProcessReceivedEpoch(1000, 1500);
+
+            // several messages from one bundle must not push earlier bundles out of the window
+            sut.ProcessReceivedEpoch(2100, 2500);
+            sut.ProcessReceivedEpoch(2101, 2500);
+            sut.ProcessReceivedEpoch(2102, 2500);
+
+            Assert.AreEqual(500, sut.ClockOffset);
+        }
+
+        private static byte[] ConstructKeepAlive(SegmentType segmentType, uint id)
+        {
+            byte[] ret = new byte[24];
+            Array.Copy(BitConverter.GetBytes((uint)ret.Length), 0, ret, 0, 4);
+            Array.Copy(BitConverter.GetBytes((ushort)segmentType), 0, ret, 12, 2);
+            Array.Copy(BitConverter.GetBytes(id), 0, ret, 16, 4);
+            Array.Copy(BitConverter.GetBytes(0x5F4E4C53u), 0, ret, 20, 4);
+            return ret;
+        }
+    }
+}
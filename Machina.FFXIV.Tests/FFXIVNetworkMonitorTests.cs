@@ -45,5 +45,59 @@ public void FFXIVNetworkMonitor_ReceiveGameData()
             Assert.IsTrue(dataSent);
 
         }
+
+        [TestMethod]
+        public void FFXIVNetworkMonitor_EncryptionDetected()
+        {
+            // uncompressed bundles containing a single keep-alive response and EncryptionInit segment
+            byte[] keepAlive = ConversionUtility.HexStringToByteArray("000000000000000000000000000000000000000000000000400000000000010000000000000000001800000000000000000000000800000014F82510AF57EB59");
+            byte[] encryptionInit = ConversionUtility.HexStringToByteArray("000000000000000000000000000000000000000000000000400000000000010000000000000000001800000000000000000000000900000014F82510AF57EB59");
+
+            int encryptionDetected = 0;
+            FFXIVNetworkMonitor sut = new();
+            sut.EncryptionDetectedEventHandler = (TCPConnection c) => encryptionDetected++;
+            TCPConnection connection = new()
+            {
+                LocalIP = 1,
+                LocalPort = 2,
+                RemoteIP = 3,
+                RemotePort = 4
+            };
+
+            sut.ProcessReceivedMessage(connection, keepAlive);
+            Assert.IsFalse(sut.IsEncrypted(connection));
+
+            sut.ProcessSentMessage(connection, encryptionInit);
+            Assert.IsTrue(sut.IsEncrypted(connection));
+
+            sut.ProcessReceivedMessage(connection, encryptionInit);
+            Assert.AreEqual(1, encryptionDetected);
+
+            sut.ProcessConnectionClosed(connection);
+            Assert.IsFalse(sut.IsEncrypted(connection));
+        }
+
+        [TestMethod]
+        public void FFXIVNetworkMonitor_ProcessConnectionClosed()
+        {
+            FFXIVNetworkMonitor sut = new();
+            TCPConnection connection = new()
+            {
+                LocalIP = 1,
+                LocalPort = 2,
+                RemoteIP = 3,
+                RemotePort = 4
+            };
+
+            sut.ProcessSentMessage(connection, new byte[] { 1, 2, 3 });
+            sut.ProcessReceivedMessage(connection, new byte[] { 1, 2, 3 });
+
+            sut.ProcessConnectionClosed(connection);
+            Assert.AreEqual(1, sut.RemovedConnections);
+
+            // nothing is left to release
+            sut.ProcessConnectionClosed(connection);
+            Assert.AreEqual(1, sut.RemovedConnections);
+        }
     }
 }
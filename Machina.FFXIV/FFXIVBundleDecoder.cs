@@ -33,6 +33,12 @@ public class FFXIVBundleDecoder
 
         public Queue<Tuple<long, byte[]>> Messages = new Queue<Tuple<long, byte[]>>(20);
 
+        /// <summary>
+        /// Maximum bundle length in bytes to accept from a bundle header.  Longer bundles are treated as corrupt, and decoding resumes at the next bundle header.
+        /// </summary>
+        public int MaxBundleLength
+        { get; set; } = 1024 * 128;
+
         public DateTime LastMessageTimestamp
         { get; set; } = DateTime.MinValue;
 
@@ -83,6 +89,14 @@ public unsafe void StoreData(byte[] buffer)
                             continue;
                         }
 
+                    // do not wait for data that a corrupted header claims is coming
+                    if (header.length > MaxBundleLength)
+                    {
+                        Trace.WriteLine($"FFXIVBundleDecoder: Bundle length [{header.length}] exceeds maximum [{MaxBundleLength}], skipping to next bundle.", "DEBUG-MACHINA");
+                        offset = ResetStream(offset);
+                        continue;
+                    }
+
                     // Exit if not all of the message is available yet.
                     if (header.length > _bundleBuffer.Length - offset)
                     {
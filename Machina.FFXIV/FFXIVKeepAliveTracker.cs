@@ -0,0 +1,159 @@
+﻿// Copyright © 2021 Ravahn - All Rights Reserved
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY. without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see<http://www.gnu.org/licenses/>.
+
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using Machina.FFXIV.Headers;
+
+namespace Machina.FFXIV
+{
+    /// <summary>
+    /// Measures the round-trip time of a connection by matching keep-alive segments sent by the game client with the
+    ///   responses returned by the server.  This is the latency experienced by the game itself.  Note that messages are
+    ///   timestamped when they are processed, so the accuracy is limited by the polling interval of the network monitor.
+    ///   The server timestamps of received bundles are also used to estimate the offset between the server and local clocks.
+    /// </summary>
+    public class FFXIVKeepAliveTracker
+    {
+        /// <summary>
+        /// Maximum number of unanswered keep-alive requests to remember
+        /// </summary>
+        public int MaxPendingRequests
+        { get; set; } = 10;
+
+        /// <summary>
+        /// Most recently measured round-trip time in milliseconds, or -1 if no response has been matched yet
+        /// </summary>
+        public long LastRoundTripTime
+        { get; private set; } = -1;
+
+        /// <summary>
+        /// Number of recently received bundles used to estimate the clock offset
+        /// </summary>
+        public int MaxClockOffsetSamples
+        { get; set; } = 50;
+
+        /// <summary>
+        /// Estimated server time minus local time in milliseconds, or null if no bundle with a server timestamp has been received yet.
+        ///   Bundles are assumed to have been sent half of the most recent round-trip time before they arrived.
+        /// </summary>
+        public long? ClockOffset
+        {
+            get
+            {
+                if (!_maxClockOffsetSample.HasValue)
+                    return null;
+
+                // the bundle was sent roughly half a round-trip before it arrived
+                return _maxClockOffsetSample.Value + (LastRoundTripTime > 0 ? LastRoundTripTime / 2 : 0);
+            }
+        }
+
+        // id and local timestamp of keep-alive requests that have not been answered yet, oldest first
+        private readonly List<KeyValuePair<uint, long>> _pendingRequests = new List<KeyValuePair<uint, long>>();
+
+        // server epoch minus local arrival time of each recently received bundle, oldest first.  These are not corrected for
+        //   latency, so that a later round-trip measurement applies to all of them.
+        private readonly Queue<long> _clockOffsetSamples = new Queue<long>();
+
+        // largest value in _clockOffsetSamples, or null if there are none
+        private long? _maxClockOffsetSample;
+
+        // server epoch of the most recently sampled bundle.  Every message in a bundle carries the same epoch.
+        private long _lastEpoch;
+
+        /// <summary>
+        /// Records a keep-alive request sent by the game client.
+        /// </summary>
+        /// <param name="timestamp">local time the message was processed, in milliseconds since the unix epoch</param>
+        /// <param name="message">message as returned by the FFXIVBundleDecoder</param>
+        /// <returns>true if the message is a keep-alive request</returns>
+        public bool ProcessSentMessage(long timestamp, byte[] message)
+        {
+            if (!TryGetKeepAlive(message, SegmentType.KeepAlive, out KeepAliveSegment keepAlive))
+                return false;
+
+            while (_pendingRequests.Count > 0 && _pendingRequests.Count >= MaxPendingRequests)
+                _pendingRequests.RemoveAt(0);
+
+            _pendingRequests.Add(new KeyValuePair<uint, long>(keepAlive.ID, timestamp));
+            return true;
+        }
+
+        /// <summary>
+        /// Matches a keep-alive response received from the server with the original request.
+        /// </summary>
+        /// <param name="timestamp">local time the message was processed, in milliseconds since the unix epoch</param>
+        /// <param name="message">message as returned by the FFXIVBundleDecoder</param>
+        /// <param name="roundTripTime">round-trip time in milliseconds, or -1 if the message was not a response to a known request</param>
+        /// <returns>true if the round-trip time was measured</returns>
+        public bool ProcessReceivedMessage(long timestamp, byte[] message, out long roundTripTime)
+        {
+            roundTripTime = -1;
+
+            if (!TryGetKeepAlive(message, SegmentType.KeepAliveResponse, out KeepAliveSegment keepAlive))
+                return false;
+
+            uint id = keepAlive.ID;
+            int index = _pendingRequests.FindIndex(x => x.Key == id);
+            if (index < 0)
+                return false;
+
+            roundTripTime = Math.Max(0, timestamp - _pendingRequests[index].Value);
+            LastRoundTripTime = roundTripTime;
+
+            // responses arrive in order, so any earlier requests will not be answered.
+            _pendingRequests.RemoveRange(0, index + 1);
+
+            return true;
+        }
+
+        /// <summary>
+        /// Records the server timestamp of a received message to update the clock offset estimate.  Only the first message of each
+        ///   bundle is sampled.  Delays in processing only make bundles appear to arrive later than they did, so the largest recent
+        ///   sample is the most accurate one.
+        /// </summary>
+        /// <param name="timestamp">local time the message was processed, in milliseconds since the unix epoch</param>
+        /// <param name="epoch">server time from the bundle header, in milliseconds since the unix epoch</param>
+        public void ProcessReceivedEpoch(long timestamp, long epoch)
+        {
+            if (epoch <= 0 || epoch == _lastEpoch)
+                return;
+            _lastEpoch = epoch;
+
+            while (_clockOffsetSamples.Count > 0 && _clockOffsetSamples.Count >= MaxClockOffsetSamples)
+                _ = _clockOffsetSamples.Dequeue();
+
+            _clockOffsetSamples.Enqueue(epoch - timestamp);
+            _maxClockOffsetSample = _clockOffsetSamples.Max();
+        }
+
+        private static unsafe bool TryGetKeepAlive(byte[] message, SegmentType segmentType, out KeepAliveSegment keepAlive)
+        {
+            keepAlive = default;
+
+            if (message == null || message.Length < sizeof(KeepAliveSegment))
+                return false;
+
+            fixed (byte* ptr = message)
+            {
+                keepAlive = *(KeepAliveSegment*)ptr;
+            }
+
+            return keepAlive.SegmentType == segmentType;
+        }
+    }
+}
@@ -16,6 +16,7 @@
 using System;
 using System.Collections.Generic;
 using System.Net;
+using Machina.FFXIV.Headers;
 using Machina.Infrastructure;
 
 namespace Machina.FFXIV
@@ -73,6 +74,48 @@ public string WindowName
         public TCPNetworkMonitorConfig.RPCapConf RPCap
         { get; set; } = new TCPNetworkMonitorConfig.RPCapConf();
 
+        /// <summary>
+        /// Specifies the time in milliseconds to wait between reading captured data.
+        /// </summary>
+        public int PollingInterval
+        { get; set; } = 30;
+
+        /// <summary>
+        /// Specifies the minimum time in milliseconds between lookups of the game process and its connections.  0 looks them up every time data is polled.
+        /// </summary>
+        public int ConnectionRefreshInterval
+        { get; set; }
+
+        /// <summary>
+        /// Specifies the time in milliseconds to wait for missing TCP data before dropping buffered packets and resetting the stream.  Must be greater than zero.
+        /// </summary>
+        public int TCPGapTimeout
+        { get; set; } = 2000;
+
+        /// <summary>
+        /// Specifies the maximum number of TCP payload bytes to buffer per direction while waiting for missing data.  Must be greater than zero.
+        /// </summary>
+        public int TCPReassemblyWindow
+        { get; set; } = 1024 * 1024;
+
+        /// <summary>
+        /// Specifies the maximum number of IP fragments to buffer per direction while waiting for the rest of a fragmented packet.  Must be greater than zero.
+        /// </summary>
+        public int IPMaxFragments
+        { get; set; } = 100;
+
+        /// <summary>
+        /// Specifies the maximum length in bytes of a single FFXIV bundle.  Headers claiming a longer bundle are treated as corrupt.  Must be greater than zero.
+        /// </summary>
+        public int MaxBundleLength
+        { get; set; } = 1024 * 128;
+
+        /// <summary>
+        /// Number of closed connections whose decoders, keep-alive tracker and encryption flag have been released
+        /// </summary>
+        public long RemovedConnections
+        { get; private set; }
+
         public Oodle.OodleImplementation OodleImplementation
         { get; set; } = Oodle.OodleImplementation.Ffxiv;
 
@@ -101,6 +144,42 @@ public void OnMessageSent(TCPConnection connection, long epoch, byte[] message)
             MessageSentEventHandler?.Invoke(connection, epoch, message);
         }
 
+        public delegate void LatencyMeasuredDelegate(TCPConnection connection, long roundTripTime);
+
+        /// <summary>
+        /// Specifies the delegate that is called when the server responds to a keep-alive sent by the game, with the round-trip time in milliseconds.
+        /// </summary>
+        public LatencyMeasuredDelegate LatencyMeasuredEventHandler;
+
+        public void OnLatencyMeasured(TCPConnection connection, long roundTripTime)
+        {
+            LatencyMeasuredEventHandler?.Invoke(connection, roundTripTime);
+        }
+
+        public delegate void ClockOffsetMeasuredDelegate(TCPConnection connection, long clockOffset);
+
+        /// <summary>
+        /// Specifies the delegate that is called with the estimated server time minus local time, in milliseconds, each time the latency is measured.
+        /// </summary>
+        public ClockOffsetMeasuredDelegate ClockOffsetMeasuredEventHandler;
+
+        public void OnClockOffsetMeasured(TCPConnection connection, long clockOffset)
+        {
+            ClockOffsetMeasuredEventHandler?.Invoke(connection, clockOffset);
+        }
+
+        public delegate void EncryptionDetectedDelegate(TCPConnection connection);
+
+        /// <summary>
+        /// Specifies the delegate that is called the first time an EncryptionInit segment is seen on a connection.  Segments after it are encrypted and cannot be parsed.
+        /// </summary>
+        public EncryptionDetectedDelegate EncryptionDetectedEventHandler;
+
+        public void OnEncryptionDetected(TCPConnection connection)
+        {
+            EncryptionDetectedEventHandler?.Invoke(connection);
+        }
+
         #endregion
 
         private TCPNetworkMonitor _monitor;
@@ -108,6 +187,8 @@ public void OnMessageSent(TCPConnection connection, long epoch, byte[] message)
 
         private readonly Dictionary<string, FFXIVBundleDecoder> _sentDecoders = new Dictionary<string, FFXIVBundleDecoder>();
         private readonly Dictionary<string, FFXIVBundleDecoder> _receivedDecoders = new Dictionary<string, FFXIVBundleDecoder>();
+        private readonly Dictionary<string, FFXIVKeepAliveTracker> _keepAliveTrackers = new Dictionary<string, FFXIVKeepAliveTracker>();
+        private readonly HashSet<string> _encryptedConnections = new HashSet<string>();
 
         /// <summary>
         /// Validates the parameters and starts the monitor.
@@ -122,6 +203,8 @@ public void Start()
 
             if (MessageReceivedEventHandler == null)
                 throw new ArgumentException("MessageReceived delegate must be specified.");
+            if (MaxBundleLength <= 0)
+                throw new ArgumentException("MaxBundleLength must be greater than zero.");
 
             _monitor = new TCPNetworkMonitor();
             _monitor.Config.ProcessID = ProcessID;
@@ -132,9 +215,15 @@ public void Start()
             _monitor.Config.LocalIP = LocalIP;
             _monitor.Config.UseRemoteIpFilter = UseRemoteIpFilter;
             _monitor.Config.RPCap = RPCap;
+            _monitor.Config.PollingInterval = PollingInterval;
+            _monitor.Config.ConnectionRefreshInterval = ConnectionRefreshInterval;
+            _monitor.Config.TCPGapTimeout = TCPGapTimeout;
+            _monitor.Config.TCPReassemblyWindow = TCPReassemblyWindow;
+            _monitor.Config.IPMaxFragments = IPMaxFragments;
 
             _monitor.DataSentEventHandler = (TCPConnection connection, byte[] data) => ProcessSentMessage(connection, data);
             _monitor.DataReceivedEventHandler = (TCPConnection connection, byte[] data) => ProcessReceivedMessage(connection, data);
+            _monitor.ConnectionClosedEventHandler = (TCPConnection connection) => ProcessConnectionClosed(connection);
 
             Oodle.OodleFactory.SetImplementation(OodleImplementation, OodlePath);
 
@@ -148,23 +237,30 @@ public void Stop()
         {
             _monitor.DataSentEventHandler = null;
             _monitor.DataReceivedEventHandler = null;
+            _monitor.ConnectionClosedEventHandler = null;
             _monitor.Stop();
             _monitor.Dispose();
             _monitor = null;
 
             _sentDecoders.Clear();
             _receivedDecoders.Clear();
+            _keepAliveTrackers.Clear();
+            lock (_encryptedConnections)
+                _encryptedConnections.Clear();
         }
 
         public void ProcessSentMessage(TCPConnection connection, byte[] data)
         {
             Tuple<long, byte[]> message;
             if (!_sentDecoders.ContainsKey(connection.ID))
-                _sentDecoders.Add(connection.ID, new FFXIVBundleDecoder());
+                _sentDecoders.Add(connection.ID, new FFXIVBundleDecoder() { MaxBundleLength = MaxBundleLength });
 
             _sentDecoders[connection.ID].StoreData(data);
             while ((message = _sentDecoders[connection.ID].GetNextFFXIVMessage()) != null)
             {
+                _ = GetKeepAliveTracker(connection).ProcessSentMessage(DateTimeOffset.UtcNow.ToUnixTimeMilliseconds(), message.Item2);
+                CheckEncryption(connection, message.Item2);
+
                 OnMessageSent(connection, message.Item1, message.Item2);
             }
         }
@@ -173,16 +269,78 @@ public void ProcessReceivedMessage(TCPConnection connection, byte[] data)
         {
             Tuple<long, byte[]> message;
             if (!_receivedDecoders.ContainsKey(connection.ID))
-                _receivedDecoders.Add(connection.ID, new FFXIVBundleDecoder());
+                _receivedDecoders.Add(connection.ID, new FFXIVBundleDecoder() { MaxBundleLength = MaxBundleLength });
 
             _receivedDecoders[connection.ID].StoreData(data);
             while ((message = _receivedDecoders[connection.ID].GetNextFFXIVMessage()) != null)
             {
+                long timestamp = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds();
+                FFXIVKeepAliveTracker tracker = GetKeepAliveTracker(connection);
+
+                tracker.ProcessReceivedEpoch(timestamp, message.Item1);
+                if (tracker.ProcessReceivedMessage(timestamp, message.Item2, out long roundTripTime))
+                {
+                    OnLatencyMeasured(connection, roundTripTime);
+                    if (tracker.ClockOffset.HasValue)
+                        OnClockOffsetMeasured(connection, tracker.ClockOffset.Value);
+                }
+                CheckEncryption(connection, message.Item2);
+
                 OnMessageReceived(connection, message.Item1, message.Item2);
             }
 
         }
 
+        /// <summary>
+        /// Returns true if an EncryptionInit segment has been seen in either direction on the connection, meaning that later segments
+        ///   are encrypted and cannot be parsed.
+        /// </summary>
+        public bool IsEncrypted(TCPConnection connection)
+        {
+            lock (_encryptedConnections)
+                return _encryptedConnections.Contains(connection.ID);
+        }
+
+        /// <summary>
+        /// Releases the decoders, keep-alive tracker and encryption flag of a connection that has closed.
+        /// </summary>
+        public void ProcessConnectionClosed(TCPConnection connection)
+        {
+            bool removed = _sentDecoders.Remove(connection.ID);
+            removed |= _receivedDecoders.Remove(connection.ID);
+            removed |= _keepAliveTrackers.Remove(connection.ID);
+            lock (_encryptedConnections)
+                removed |= _encryptedConnections.Remove(connection.ID);
+
+            if (removed)
+                RemovedConnections++;
+        }
+
+        private void CheckEncryption(TCPConnection connection, byte[] message)
+        {
+            // segment type immediately follows the message length, actor id and login user id
+            if (message.Length < 14 || BitConverter.ToUInt16(message, 12) != (ushort)SegmentType.EncryptionInit)
+                return;
+
+            bool added;
+            lock (_encryptedConnections)
+                added = _encryptedConnections.Add(connection.ID);
+
+            if (added)
+                OnEncryptionDetected(connection);
+        }
+
+        private FFXIVKeepAliveTracker GetKeepAliveTracker(TCPConnection connection)
+        {
+            if (!_keepAliveTrackers.TryGetValue(connection.ID, out FFXIVKeepAliveTracker tracker))
+            {
+                tracker = new FFXIVKeepAliveTracker();
+                _keepAliveTrackers.Add(connection.ID, tracker);
+            }
+
+            return tracker;
+        }
+
         protected virtual void Dispose(bool disposing)
         {
             if (!_disposedValue)
@@ -0,0 +1,42 @@
+﻿// Copyright © 2021 Ravahn - All Rights Reserved
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY. without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see<http://www.gnu.org/licenses/>.
+
+using System.Runtime.InteropServices;
+
+namespace Machina.FFXIV.Headers
+{
+    /// <summary>
+    /// Keep-alive segment.  The layout is the same in both directions: either side may send a KeepAlive, and the other side
+    ///   echoes it back with the same ID and timestamp as a KeepAliveResponse.
+    /// </summary>
+    [StructLayout(LayoutKind.Explicit)]
+    public struct KeepAliveSegment
+    {
+        [FieldOffset(0)]
+        public uint MessageLength;
+        [FieldOffset(4)]
+        public uint ActorID;
+        [FieldOffset(8)]
+        public uint LoginUserID;
+        [FieldOffset(12)]
+        public SegmentType SegmentType;
+        [FieldOffset(14)]
+        public ushort Padding;
+        [FieldOffset(16)]
+        public uint ID;
+        [FieldOffset(20)]
+        public uint Timestamp;
+    }
+}
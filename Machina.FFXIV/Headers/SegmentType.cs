@@ -0,0 +1,29 @@
+﻿// Copyright © 2021 Ravahn - All Rights Reserved
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY. without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see<http://www.gnu.org/licenses/>.
+
+namespace Machina.FFXIV.Headers
+{
+    /// <summary>
+    /// Enumerates the known segment types within a bundle.  Note that names were adopted from the Sapphire project
+    /// </summary>
+    public enum SegmentType : ushort
+    {
+        SessionInit = 0x01,
+        IPC = 0x03,
+        KeepAlive = 0x07,
+        KeepAliveResponse = 0x08,
+        EncryptionInit = 0x09
+    }
+}
@@ -0,0 +1,82 @@
+﻿// Copyright © 2021 Ravahn - All Rights Reserved
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY. without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see<http://www.gnu.org/licenses/>.
+
+using System;
+using System.Collections.Generic;
+using System.Net;
+using System.Net.Sockets;
+using Machina.Infrastructure;
+using Machina.Tests.Utility;
+using Microsoft.VisualStudio.TestTools.UnitTesting;
+
+namespace Machina.Tests
+{
+    [TestClass()]
+    public class ConnectionManagerTests
+    {
+        [TestCleanup]
+        public void TestCleanup()
+        {
+            TestInfrastructure.Listener.Messages.Clear();
+        }
+
+        [TestMethod()]
+        public void ConnectionManager_Refresh_ReportsClosedConnectionsWhenCaptureFails()
+        {
+            // open a loopback connection, so that the refresh finds a new connection to start capturing
+            TcpListener listener = new(IPAddress.Loopback, 0);
+            listener.Start();
+            using TcpClient client = new();
+            client.Connect(IPAddress.Loopback, ((IPEndPoint)listener.LocalEndpoint).Port);
+
+            using ConnectionManager sut = new();
+            sut.Config.ProcessID = (uint)Environment.ProcessId;
+            sut.Config.MonitorType = NetworkMonitorType.WinPCap;
+            // no rpcap server is listening, so starting capture fails after the closed connection has been removed
+            sut.Config.RPCap.host = "127.0.0.1";
+            sut.Config.RPCap.port = 1;
+
+            TCPConnection closedConnection = new()
+            {
+                LocalIP = ConversionUtility.IPStringToUint("127.0.0.2"),
+                LocalPort = 1,
+                RemoteIP = ConversionUtility.IPStringToUint("127.0.0.3"),
+                RemotePort = 1
+            };
+            sut.Connections.Add(closedConnection);
+
+            List<TCPConnection> closed = new();
+            sut.ConnectionClosedEventHandler = (TCPConnection connection) => closed.Add(connection);
+
+            bool thrown = false;
+            try
+            {
+                sut.Refresh();
+            }
+            catch (Exception)
+            {
+                thrown = true;
+            }
+            finally
+            {
+                listener.Stop();
+            }
+
+            Assert.IsTrue(thrown);
+            Assert.AreEqual(1, closed.Count);
+            Assert.AreEqual(closedConnection, closed[0]);
+        }
+    }
+}
@@ -481,6 +481,93 @@ public void IPDecoder_GetNextIPPayload_SingleFragmentTimeout()
             Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
         }
 
+        /// <summary>
+        /// this tests that the oldest fragments are dropped once MaxFragments is reached.
+        /// </summary>
+        [TestMethod]
+        public void IPDecoder_FilterAndStoreData_MaxFragments()
+        {
+            uint sourceIP = ConversionUtility.IPStringToUint("1.2.3.4");
+            uint destinationIP = ConversionUtility.IPStringToUint("2.3.4.5");
+
+            IPDecoder sut = new(sourceIP, destinationIP, IPProtocol.TCP)
+            {
+                MaxFragments = 2
+            };
+
+            for (ushort id = 1111; id < 1114; id++)
+            {
+                byte[] data = ConstructIP4Packet(
+                    4, id, (byte)IPFragment.MF, 0, IPProtocol.TCP,
+                    sourceIP,
+                    destinationIP,
+                    new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 }
+                );
+                sut.FilterAndStoreData(data, data.Length);
+            }
+
+            Assert.AreEqual(2, sut.Fragments.Count);
+            Assert.AreEqual(1, sut.DroppedFragments);
+            Assert.AreEqual(1112, ConversionUtility.ntohs(BitConverter.ToUInt16(sut.Fragments[0], 4)));
+            Assert.AreEqual(1, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        /// <summary>
+        /// this tests that every fragment sharing the oldest ID is dropped once MaxFragments is reached.
+        /// </summary>
+        [TestMethod]
+        public void IPDecoder_FilterAndStoreData_MaxFragmentsDropsWholePayload()
+        {
+            uint sourceIP = ConversionUtility.IPStringToUint("1.2.3.4");
+            uint destinationIP = ConversionUtility.IPStringToUint("2.3.4.5");
+
+            byte[] data1 = ConstructIP4Packet(
+                4, 1111, (byte)IPFragment.MF, 0, IPProtocol.TCP,
+                sourceIP,
+                destinationIP,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16 }
+            );
+
+            byte[] data2 = ConstructIP4Packet(
+                4, 1112, (byte)IPFragment.MF, 0, IPProtocol.TCP,
+                sourceIP,
+                destinationIP,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16 }
+            );
+
+            byte[] data3 = ConstructIP4Packet(
+                4, 1111, (byte)IPFragment.MF, 16, IPProtocol.TCP,
+                sourceIP,
+                destinationIP,
+                new byte[] { 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32 }
+            );
+
+            byte[] data4 = ConstructIP4Packet(
+                4, 1113, (byte)IPFragment.MF, 0, IPProtocol.TCP,
+                sourceIP,
+                destinationIP,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16 }
+            );
+
+            IPDecoder sut = new(sourceIP, destinationIP, IPProtocol.TCP)
+            {
+                MaxFragments = 3
+            };
+
+            sut.FilterAndStoreData(data1, data1.Length);
+            sut.FilterAndStoreData(data2, data2.Length);
+            sut.FilterAndStoreData(data3, data3.Length);
+            Assert.AreEqual(3, sut.Fragments.Count);
+            Assert.AreEqual(0, sut.DroppedFragments);
+
+            sut.FilterAndStoreData(data4, data4.Length);
+            Assert.AreEqual(2, sut.Fragments.Count);
+            Assert.AreEqual(2, sut.DroppedFragments);
+            Assert.AreEqual(1112, ConversionUtility.ntohs(BitConverter.ToUInt16(sut.Fragments[0], 4)));
+            Assert.AreEqual(1113, ConversionUtility.ntohs(BitConverter.ToUInt16(sut.Fragments[1], 4)));
+            Assert.AreEqual(1, TestInfrastructure.Listener.Messages.Count);
+        }
+
         /// <summary>
         /// this tests that a single IP fragment and a single packet will return just the packet.
         /// </summary>
@@ -318,6 +318,258 @@ public void TCPDecoder_GetNextTCPPacket_SynAndPacket()
             Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
         }
 
+        [TestMethod]
+        public void TCPDecoder_GetNextTCPPacket_Retransmit()
+        {
+            ushort source_port = 111;
+            ushort dest_port = 222;
+
+            byte[] packet = ConstructTCPPacket(
+                source_port, dest_port,
+                1111,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            TCPDecoder sut = new(source_port, dest_port);
+
+            sut.FilterAndStoreData(packet);
+            byte[] ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(10, ret?.Length);
+
+            sut.FilterAndStoreData(packet);
+            Assert.AreEqual(0, sut.Packets.Count);
+            ret = sut.GetNextTCPDatagram();
+            Assert.IsNull(ret);
+            Assert.AreEqual(1, sut.Statistics.DuplicatePackets);
+            Assert.AreEqual(10, sut.Statistics.DuplicateBytes);
+            Assert.AreEqual(0, sut.Statistics.DroppedPackets);
+            Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        [TestMethod]
+        public void TCPDecoder_GetNextTCPPacket_DuplicateBeforeReassembly()
+        {
+            ushort source_port = 111;
+            ushort dest_port = 222;
+
+            byte[] packet1 = ConstructTCPPacket(
+                source_port, dest_port,
+                1111,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            byte[] packet2 = ConstructTCPPacket(
+                source_port, dest_port,
+                1111,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            TCPDecoder sut = new(source_port, dest_port);
+
+            sut.FilterAndStoreData(packet1);
+            sut.FilterAndStoreData(packet2);
+            Assert.AreEqual(1, sut.Packets.Count);
+
+            byte[] ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(10, ret?.Length);
+            Assert.IsNull(sut.GetNextTCPDatagram());
+            Assert.AreEqual(1, sut.Statistics.DuplicatePackets);
+            Assert.AreEqual(10, sut.Statistics.DuplicateBytes);
+            Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        [TestMethod]
+        public void TCPDecoder_GetNextTCPPacket_OverlappingPackets()
+        {
+            ushort source_port = 111;
+            ushort dest_port = 222;
+
+            byte[] packet1 = ConstructTCPPacket(
+                source_port, dest_port,
+                1111,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            // overlaps the last 5 bytes of the first packet
+            byte[] packet2 = ConstructTCPPacket(
+                source_port, dest_port,
+                1116,
+                1131,
+                (byte)TCPOptions.PSH,
+                new byte[] { 6, 7, 8, 9, 10, 11, 12, 13, 14, 15 });
+
+            TCPDecoder sut = new(source_port, dest_port);
+
+            sut.FilterAndStoreData(packet1);
+            sut.FilterAndStoreData(packet2);
+            Assert.AreEqual(2, sut.Packets.Count);
+
+            byte[] ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(10, ret?.Length);
+            for (int i = 0; i < 10; i++)
+                Assert.AreEqual(i + 1, ret[i]);
+            Assert.AreEqual(1, sut.Packets.Count);
+
+            ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(5, ret?.Length);
+            for (int i = 0; i < 5; i++)
+                Assert.AreEqual(i + 11, ret[i]);
+            Assert.AreEqual(0, sut.Packets.Count);
+            Assert.AreEqual(0, sut.Statistics.DuplicatePackets);
+            Assert.AreEqual(5, sut.Statistics.DuplicateBytes);
+            Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        [TestMethod]
+        public void TCPDecoder_GetNextTCPPacket_SequenceWraparound()
+        {
+            ushort source_port = 111;
+            ushort dest_port = 222;
+
+            byte[] packet1 = ConstructTCPPacket(
+                source_port, dest_port,
+                0xFFFFFFF0,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            byte[] packet2 = ConstructTCPPacket(
+                source_port, dest_port,
+                0xFFFFFFFA,
+                1121,
+                0,
+                new byte[] { 11, 12, 13, 14, 15, 16, 17, 18, 19, 20 });
+
+            byte[] packet3 = ConstructTCPPacket(
+                source_port, dest_port,
+                4,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 21, 22, 23, 24, 25, 26, 27, 28, 29, 30 });
+
+            TCPDecoder sut = new(source_port, dest_port);
+
+            sut.FilterAndStoreData(packet1);
+            byte[] ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(10, ret?.Length);
+
+            sut.FilterAndStoreData(packet3);
+            Assert.IsNull(sut.GetNextTCPDatagram());
+            Assert.AreEqual(1, sut.Packets.Count);
+
+            sut.FilterAndStoreData(packet2);
+            ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(20, ret?.Length);
+            for (int i = 0; i < 20; i++)
+                Assert.AreEqual(i + 11, ret[i]);
+            Assert.AreEqual(0, sut.Packets.Count);
+            Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        [TestMethod]
+        public void TCPDecoder_GetNextTCPPacket_NextSequenceZero()
+        {
+            ushort source_port = 111;
+            ushort dest_port = 222;
+
+            byte[] packet1 = ConstructTCPPacket(
+                source_port, dest_port,
+                0xFFFFFFF6,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            byte[] packet2 = ConstructTCPPacket(
+                source_port, dest_port,
+                0,
+                1121,
+                0,
+                new byte[] { 11, 12, 13, 14, 15, 16, 17, 18, 19, 20 });
+
+            byte[] packet3 = ConstructTCPPacket(
+                source_port, dest_port,
+                10,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 21, 22, 23, 24, 25, 26, 27, 28, 29, 30 });
+
+            TCPDecoder sut = new(source_port, dest_port);
+
+            sut.FilterAndStoreData(packet1);
+            byte[] ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(10, ret?.Length);
+
+            // next sequence is now zero - a retransmit must still be discarded, and must not restart the stream
+            sut.FilterAndStoreData(packet1);
+            Assert.AreEqual(0, sut.Packets.Count);
+            Assert.AreEqual(1, sut.Statistics.DuplicatePackets);
+
+            sut.FilterAndStoreData(packet3);
+            Assert.IsNull(sut.GetNextTCPDatagram());
+            Assert.AreEqual(1, sut.Packets.Count);
+
+            sut.FilterAndStoreData(packet2);
+            ret = sut.GetNextTCPDatagram();
+            Assert.AreEqual(20, ret?.Length);
+            for (int i = 0; i < 20; i++)
+                Assert.AreEqual(i + 11, ret[i]);
+            Assert.AreEqual(0, sut.Packets.Count);
+            Assert.AreEqual(0, TestInfrastructure.Listener.Messages.Count);
+        }
+
+        [TestMethod]
+        public void TCPDecoder_GetNextTCPPacket_ReassemblyWindowExceeded()
+        {
+            ushort source_port = 111;
+            ushort dest_port = 222;
+
+            byte[] packet1 = ConstructTCPPacket(
+                source_port, dest_port,
+                1111,
+                1121,
+                (byte)TCPOptions.PSH,
+                new byte[] { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 });
+
+            byte[] packet2 = ConstructTCPPacket(
+                source_port, dest_port,
+                1131,
+                1141,
+                (byte)TCPOptions.PSH,
+                new byte[] { 11, 12, 13, 14, 15, 16, 17, 18, 19, 20 });
+
+            byte[] packet3 = ConstructTCPPacket(
+                source_port, dest_port,
+                1141,
+                1151,
+                (byte)TCPOptions.PSH,
+                new byte[] { 21, 22, 23, 24, 25, 26, 27, 28, 29, 30 });
+
+            TCPDecoder sut = new(source_port, dest_port)
+            {
+                ReassemblyWindow = 15
+            };
+
+            sut.FilterAndStoreData(packet1);
+            Assert.AreEqual(10, sut.GetNextTCPDatagram()?.Length);
+
+            sut.FilterAndStoreData(packet2);
+            Assert.IsNull(sut.GetNextTCPDatagram());
+            Assert.AreEqual(1, sut.Packets.Count);
+            Assert.AreEqual(0, sut.Statistics.StreamResets);
+
+            sut.FilterAndStoreData(packet3);
+            Assert.IsNull(sut.GetNextTCPDatagram());
+            Assert.AreEqual(0, sut.Packets.Count);
+            Assert.AreEqual(1, sut.Statistics.StreamResets);
+            Assert.AreEqual(2, sut.Statistics.DroppedPackets);
+            Assert.AreEqual(20, sut.Statistics.DroppedBytes);
+            Assert.IsTrue(TestInfrastructure.Listener.Messages.Count > 0);
+        }
+
         private unsafe byte[] ConstructTCPPacket(
             ushort source_port,
             ushort dest_port,
@@ -13,6 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see<http://www.gnu.org/licenses/>.
 
+using System;
 using System.Net.Http;
 using System.Threading.Tasks;
 using Machina.Infrastructure;
@@ -60,6 +61,50 @@ public void TCPNetworkMonitor_RawSocket_SendAndReceiveData()
             Assert.IsTrue(dataSentCount >= 1);
         }
 
+        [TestMethod()]
+        public void TCPNetworkMonitor_Start_InvalidPollingInterval()
+        {
+            TCPNetworkMonitor monitor = new();
+            monitor.Config.ProcessID = (uint)System.Environment.ProcessId;
+            monitor.Config.PollingInterval = 0;
+            monitor.DataReceivedEventHandler += (TCPConnection connection, byte[] data) => DataReceived();
+
+            _ = Assert.ThrowsException<ArgumentException>(() => monitor.Start());
+        }
+
+        [TestMethod]
+        public void TCPNetworkMonitor_Start_InvalidTCPGapTimeout()
+        {
+            TCPNetworkMonitor monitor = new();
+            monitor.Config.ProcessID = (uint)System.Environment.ProcessId;
+            monitor.Config.TCPGapTimeout = 0;
+            monitor.DataReceivedEventHandler += (TCPConnection connection, byte[] data) => DataReceived();
+
+            _ = Assert.ThrowsException<ArgumentException>(() => monitor.Start());
+        }
+
+        [TestMethod]
+        public void TCPNetworkMonitor_Start_InvalidTCPReassemblyWindow()
+        {
+            TCPNetworkMonitor monitor = new();
+            monitor.Config.ProcessID = (uint)System.Environment.ProcessId;
+            monitor.Config.TCPReassemblyWindow = 0;
+            monitor.DataReceivedEventHandler += (TCPConnection connection, byte[] data) => DataReceived();
+
+            _ = Assert.ThrowsException<ArgumentException>(() => monitor.Start());
+        }
+
+        [TestMethod]
+        public void TCPNetworkMonitor_Start_InvalidIPMaxFragments()
+        {
+            TCPNetworkMonitor monitor = new();
+            monitor.Config.ProcessID = (uint)System.Environment.ProcessId;
+            monitor.Config.IPMaxFragments = 0;
+            monitor.DataReceivedEventHandler += (TCPConnection connection, byte[] data) => DataReceived();
+
+            _ = Assert.ThrowsException<ArgumentException>(() => monitor.Start());
+        }
+
         private void DataReceived()
         {
             dataReceivedCount++;
@@ -30,40 +30,84 @@ public class ConnectionManager : IDisposable
         public IList<TCPConnection> Connections { get; } = new List<TCPConnection>(2);
 
 
+        public delegate void ConnectionClosedDelegate(TCPConnection connection);
+
+        /// <summary>
+        /// Specifies the delegate that is called for each connection removed during Refresh().
+        /// </summary>
+        public ConnectionClosedDelegate ConnectionClosedEventHandler;
+
+        public void OnConnectionClosed(TCPConnection connection)
+        {
+            ConnectionClosedEventHandler?.Invoke(connection);
+        }
+
         private readonly ProcessTCPInfo _processTCPInfo = new ProcessTCPInfo();
         private bool _disposedValue;
 
+        // connections that existed before the current refresh, used to detect closed connections
+        private readonly List<TCPConnection> _previousConnections = new List<TCPConnection>();
+
         public void Refresh()
         {
-            // Update any filters
-            _processTCPInfo.ProcessID = Config.ProcessID;
-            _processTCPInfo.ProcessIDList = Config.ProcessIDList;
-            _processTCPInfo.ProcessWindowName = Config.WindowName;
-            _processTCPInfo.ProcessWindowClass = Config.WindowClass;
-            _processTCPInfo.LocalIP = Config.LocalIP;
-
-            // todo: do not pass in current connections?
-            // get any active game connections
-            _processTCPInfo.UpdateTCPIPConnections(Connections);
-
-            foreach (TCPConnection connection in Connections)
+            _previousConnections.Clear();
+            _previousConnections.AddRange(Connections);
+
+            try
             {
-                if (connection.Socket == null)
+                // Update any filters
+                _processTCPInfo.ProcessID = Config.ProcessID;
+                _processTCPInfo.ProcessIDList = Config.ProcessIDList;
+                _processTCPInfo.ProcessWindowName = Config.WindowName;
+                _processTCPInfo.ProcessWindowClass = Config.WindowClass;
+                _processTCPInfo.LocalIP = Config.LocalIP;
+
+                // todo: do not pass in current connections?
+                // get any active game connections
+                _processTCPInfo.UpdateTCPIPConnections(Connections);
+
+                foreach (TCPConnection connection in Connections)
                 {
-                    // Set up decoders for data sent from local machine
-                    connection.IPDecoderSend = new IPDecoder(connection.LocalIP, connection.RemoteIP, IPProtocol.TCP);
-                    connection.TCPDecoderSend = new TCPDecoder(connection.LocalPort, connection.RemotePort);
-
-                    // set up decoders for data received by local machine
-                    connection.IPDecoderReceive = new IPDecoder(connection.RemoteIP, connection.LocalIP, IPProtocol.TCP);
-                    connection.TCPDecoderReceive = new TCPDecoder(connection.RemotePort, connection.LocalPort);
-
-                    // set up socket
-                    connection.Socket = Config.MonitorType == NetworkMonitorType.WinPCap ?
-                        new PCapCaptureSocket(Config.RPCap) :
-                        (ICaptureSocket)new RawCaptureSocket();
-
-                    connection.Socket.StartCapture(connection.LocalIP, Config.UseRemoteIpFilter ? connection.RemoteIP : 0);
+                    if (connection.Socket == null)
+                    {
+                        // Set up decoders for data sent from local machine
+                        connection.IPDecoderSend = new IPDecoder(connection.LocalIP, connection.RemoteIP, IPProtocol.TCP)
+                        {
+                            MaxFragments = Config.IPMaxFragments
+                        };
+                        connection.TCPDecoderSend = new TCPDecoder(connection.LocalPort, connection.RemotePort)
+                        {
+                            GapTimeout = Config.TCPGapTimeout,
+                            ReassemblyWindow = Config.TCPReassemblyWindow
+                        };
+
+                        // set up decoders for data received by local machine
+                        connection.IPDecoderReceive = new IPDecoder(connection.RemoteIP, connection.LocalIP, IPProtocol.TCP)
+                        {
+                            MaxFragments = Config.IPMaxFragments
+                        };
+                        connection.TCPDecoderReceive = new TCPDecoder(connection.RemotePort, connection.LocalPort)
+                        {
+                            GapTimeout = Config.TCPGapTimeout,
+                            ReassemblyWindow = Config.TCPReassemblyWindow
+                        };
+
+                        // set up socket
+                        connection.Socket = Config.MonitorType == NetworkMonitorType.WinPCap ?
+                            new PCapCaptureSocket(Config.RPCap) :
+                            (ICaptureSocket)new RawCaptureSocket();
+
+                        connection.Socket.StartCapture(connection.LocalIP, Config.UseRemoteIpFilter ? connection.RemoteIP : 0);
+                    }
+                }
+            }
+            finally
+            {
+                // report connections removed by this refresh, even if starting capture on a new connection failed afterwards.
+                for (int i = 0; i < _previousConnections.Count; i++)
+                {
+                    if (!Connections.Contains(_previousConnections[i]))
+                        OnConnectionClosed(_previousConnections[i]);
                 }
             }
         }
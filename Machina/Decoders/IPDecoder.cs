@@ -58,6 +58,18 @@ public DateTime LastIPFragmentTimestamp
         { get; internal set; }
             = DateTime.MinValue;
 
+        /// <summary>
+        /// Maximum number of IP fragments to buffer.  When reached, all fragments of the oldest incomplete payload are dropped.  Must be greater than zero.
+        /// </summary>
+        public int MaxFragments
+        { get; set; } = 100;
+
+        /// <summary>
+        /// Number of fragments dropped because the MaxFragments limit was reached
+        /// </summary>
+        public long DroppedFragments
+        { get; internal set; }
+
         /// <summary>
         /// class constructor
         /// </summary>
@@ -157,6 +169,24 @@ public unsafe void FilterAndStoreData(byte[] buffer, int size)
                         // store payload
                         byte[] ret = new byte[packetLength];
                         Array.Copy(buffer, offset, ret, 0, ret.Length);
+
+                        // drop the oldest incomplete payloads if fragments are accumulating.  All fragments sharing the ID are
+                        //   removed, since the payload can no longer be completed once any of them is gone.
+                        while (Fragments.Count > 0 && Fragments.Count >= MaxFragments)
+                        {
+                            ushort oldestId = ConversionUtility.ntohs(BitConverter.ToUInt16(Fragments[0], 4));
+                            Trace.WriteLine($"IPDecoder: {MaxFragments} fragments buffered, dropping fragments with ID [{oldestId}].", "DEBUG-MACHINA");
+
+                            for (int j = Fragments.Count - 1; j >= 0; j--)
+                            {
+                                if (ConversionUtility.ntohs(BitConverter.ToUInt16(Fragments[j], 4)) == oldestId)
+                                {
+                                    Fragments.RemoveAt(j);
+                                    DroppedFragments++;
+                                }
+                            }
+                        }
+
                         Fragments.Add(ret);
                     }
 
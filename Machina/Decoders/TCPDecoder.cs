@@ -45,15 +45,39 @@ public DateTime LastPacketTimestamp
         { get; internal set; }
             = DateTime.MinValue;
 
+        /// <summary>
+        /// Time in milliseconds to wait for missing data before the buffered packets are dropped and the stream is reset
+        /// </summary>
+        public int GapTimeout
+        { get; set; } = 2000;
+
+        /// <summary>
+        /// Maximum number of payload bytes to buffer while waiting for missing data before the buffered packets are dropped and the stream is reset
+        /// </summary>
+        public int ReassemblyWindow
+        { get; set; } = 1024 * 1024;
+
+        /// <summary>
+        /// Counters for duplicate and dropped data
+        /// </summary>
+        public TCPStreamStatistics Statistics
+        { get; } = new TCPStreamStatistics();
+
         // expected sequence number of the next TCP datagram
         private uint _NextSequence;
 
+        // whether _NextSequence has been set since the decoder was created or last reset.  Zero is a valid sequence number.
+        private bool _sequenceInitialized;
+
         // source port is in network byte order
         private readonly ushort _sourcePort;
 
         // destination port is in network byte order
         private readonly ushort _destinationPort;
 
+        // packets consumed during the current call to GetNextTCPDatagram()
+        private readonly List<byte[]> _processedPackets = new List<byte[]>();
+
         /// <summary>
         /// class constructor
         /// </summary>
@@ -86,11 +110,34 @@ public unsafe void FilterAndStoreData(byte[] buffer)
                     _destinationPort != header.destination_port)
                     return;
 
+                int payloadLength = buffer.Length - header.DataOffset;
+
                 // if there is no data, we can discard the packet - this will likely be an ACK, but it shouldnt affect stream processing.
-                if (buffer.Length - header.DataOffset == 0)
+                if (payloadLength == 0)
                     if ((header.flags & (byte)TCPOptions.SYN) == 0)
                         return;
 
+                // discard retransmits of data that was already processed.  SYN packets are kept, since they may restart the stream.
+                if (_sequenceInitialized && (header.flags & (byte)TCPOptions.SYN) == 0 &&
+                    CompareSequence(header.SequenceNumber + (uint)payloadLength, _NextSequence) <= 0)
+                {
+                    Statistics.DuplicatePackets++;
+                    Statistics.DuplicateBytes += payloadLength;
+                    return;
+                }
+
+                // discard exact duplicates of packets that are still waiting for reassembly
+                for (int i = 0; i < Packets.Count; i++)
+                {
+                    if (GetSequenceNumber(Packets[i]) == header.SequenceNumber &&
+                        GetPayloadLength(Packets[i]) == payloadLength)
+                    {
+                        Statistics.DuplicatePackets++;
+                        Statistics.DuplicateBytes += payloadLength;
+                        return;
+                    }
+                }
+
                 Packets.Add(buffer);
             }
         }
@@ -105,12 +152,15 @@ public unsafe byte[] GetNextTCPDatagram()
                 return null;
 
             byte[] buffer = null;
+            _processedPackets.Clear();
 
             List<byte[]> packets = Packets.ToList();
             if (Packets.Count > 1)
             {
-                packets.Sort((x, y) => ConversionUtility.ntohl(BitConverter.ToUInt32(x, 4))
-                    .CompareTo(ConversionUtility.ntohl(BitConverter.ToUInt32(y, 4))));
+                // sort relative to the expected sequence number, so that the order is not affected by sequence number wraparound.
+                uint baseSequence = _sequenceInitialized ? _NextSequence : GetSequenceNumber(packets[0]);
+                packets.Sort((x, y) => CompareSequence(GetSequenceNumber(x), baseSequence)
+                    .CompareTo(CompareSequence(GetSequenceNumber(y), baseSequence)));
             }
             for (int i = 0; i < packets.Count; i++)
             {
@@ -119,17 +169,20 @@ public unsafe byte[] GetNextTCPDatagram()
                     TCPHeader header = *(TCPHeader*)ptr;
 
                     // failsafe - if starting, or just reset, start with next available packet.
-                    if (_NextSequence == 0)
+                    if (!_sequenceInitialized)
+                    {
                         _NextSequence = header.SequenceNumber;
+                        _sequenceInitialized = true;
+                    }
 
-                    if (header.SequenceNumber <= _NextSequence)
+                    if (CompareSequence(header.SequenceNumber, _NextSequence) <= 0)
                     {
                         LastPacketTimestamp = DateTime.UtcNow;
 
                         if ((header.flags & (byte)TCPOptions.SYN) > 0)
                         {
                             // filter out only when difference between sequence numbers is ~10k
-                            if (_NextSequence == 0 || _NextSequence == header.SequenceNumber)
+                            if (_NextSequence == header.SequenceNumber)
                                 _NextSequence = header.SequenceNumber + 1;
                             else if (Math.Abs(_NextSequence - header.SequenceNumber) > 100000)
                             {
@@ -139,12 +192,13 @@ public unsafe byte[] GetNextTCPDatagram()
                             else
                                 Trace.WriteLine($"TCPDecoder: Ignoring SYN packet new sequence number.  Current Sequence: [{_NextSequence}, sent sequence: [{header.SequenceNumber}].", "DEBUG-MACHINA");
 
+                            _processedPackets.Add(packets[i]);
                             continue; // do not process SYN packet, but set next sequence #.
                         }
 
                         // if this is a retransmit, only include the portion of data that is not already processed
                         uint packetOffset = 0;
-                        if (header.SequenceNumber < _NextSequence)
+                        if (header.SequenceNumber != _NextSequence)
                             packetOffset = _NextSequence - header.SequenceNumber;
 
                         // do not process this packet if it was previously fully processed, or has no data.
@@ -155,6 +209,9 @@ public unsafe byte[] GetNextTCPDatagram()
                             continue;
                         }
 
+                        // the start of this packet overlaps data that was already processed
+                        Statistics.DuplicateBytes += packetOffset;
+
                         if (buffer == null)
                         {
                             buffer = new byte[packets[i].Length - header.DataOffset - packetOffset];
@@ -169,42 +226,83 @@ public unsafe byte[] GetNextTCPDatagram()
 
                         // NOTE: do not need to correct for packetOffset here.
                         _NextSequence = header.SequenceNumber + (uint)packets[i].Length - header.DataOffset;
+                        _processedPackets.Add(packets[i]);
 
                         // if PUSH flag is set, return data immedately.
                         // Note: data in the TCP stream can be processed without the PSH flag set, the application must interpret the stream data.
                         if ((header.flags & (byte)TCPOptions.PSH) > 0)
                             break;
                     }
-                    else if (header.SequenceNumber > _NextSequence)
+                    else
                         break;// if the current sequence # is after the last processed packet, stop processing - missing data.  May need recovery in the future.
                 }
             }
 
-            // remove any earlier packets from the primary array
+            // remove any packets that do not contain unprocessed data from the primary array.
+            //   Packets that overlap the end of the processed data are kept, and the remainder is returned on the next call.
             for (int i = Packets.Count - 1; i >= 0; i--)
             {
-                if (ConversionUtility.ntohl(BitConverter.ToUInt32(Packets[i], 4)) < _NextSequence)
+                if (CompareSequence(GetSequenceNumber(Packets[i]) + (uint)GetPayloadLength(Packets[i]), _NextSequence) <= 0)
+                {
+                    if (!_processedPackets.Contains(Packets[i]))
+                    {
+                        Statistics.DuplicatePackets++;
+                        Statistics.DuplicateBytes += GetPayloadLength(Packets[i]);
+                    }
                     Packets.RemoveAt(i);
+                }
             }
 
             if (Packets.Count > 0)
             {
-                if (LastPacketTimestamp.AddMilliseconds(2000) < DateTime.UtcNow)
+                if (LastPacketTimestamp.AddMilliseconds(GapTimeout) < DateTime.UtcNow)
                 {
-                    Trace.WriteLine("TCPDecoder: >2 sec since last processed packet, resetting stream.", "DEBUG-MACHINA");
-
-                    for (int i = Packets.Count - 1; i >= 0; i--)
-                    {
-                        Trace.WriteLine($"TCPDecoder: Missing Sequence # [{_NextSequence}], Dropping packet with sequence # [" +
-                            $"{ConversionUtility.ntohl(BitConverter.ToUInt32(Packets[i], 4))}].", "DEBUG-MACHINA");
-                        Packets.RemoveAt(i);
-                    }
-                    _NextSequence = 0;
+                    Trace.WriteLine($"TCPDecoder: >{GapTimeout} ms since last processed packet, resetting stream.", "DEBUG-MACHINA");
+                    ResetStream();
+                }
+                else if (Packets.Sum(x => GetPayloadLength(x)) > ReassemblyWindow)
+                {
+                    Trace.WriteLine($"TCPDecoder: More than {ReassemblyWindow} bytes buffered while waiting for missing data, resetting stream.", "DEBUG-MACHINA");
+                    ResetStream();
                 }
             }
 
             return buffer;
         }
+
+        private void ResetStream()
+        {
+            for (int i = Packets.Count - 1; i >= 0; i--)
+            {
+                Trace.WriteLine($"TCPDecoder: Missing Sequence # [{_NextSequence}], Dropping packet with sequence # [" +
+                    $"{GetSequenceNumber(Packets[i])}].", "DEBUG-MACHINA");
+                Statistics.DroppedPackets++;
+                Statistics.DroppedBytes += GetPayloadLength(Packets[i]);
+                Packets.RemoveAt(i);
+            }
+            _NextSequence = 0;
+            _sequenceInitialized = false;
+            Statistics.StreamResets++;
+        }
+
+        private static uint GetSequenceNumber(byte[] packet)
+        {
+            return ConversionUtility.ntohl(BitConverter.ToUInt32(packet, 4));
+        }
+
+        private static int GetPayloadLength(byte[] packet)
+        {
+            return packet.Length - ((packet[12] >> 4) * 4);
+        }
+
+        /// <summary>
+        /// Compares two sequence numbers, taking wraparound into account.
+        /// </summary>
+        /// <returns>negative if x is before y, zero if they are equal, and positive if x is after y</returns>
+        private static int CompareSequence(uint x, uint y)
+        {
+            return unchecked((int)(x - y));
+        }
     }
 
 }
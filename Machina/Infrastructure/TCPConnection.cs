@@ -41,6 +41,26 @@ internal TCPDecoder TCPDecoderReceive
         internal TCPDecoder TCPDecoderSend
         { get; set; }
 
+        /// <summary>
+        /// Reassembly statistics for data received by the local machine
+        /// </summary>
+        public TCPStreamStatistics ReceiveStatistics => TCPDecoderReceive?.Statistics;
+
+        /// <summary>
+        /// Reassembly statistics for data sent from the local machine
+        /// </summary>
+        public TCPStreamStatistics SendStatistics => TCPDecoderSend?.Statistics;
+
+        /// <summary>
+        /// Number of IP fragments of data received by the local machine that were dropped because too many fragments were buffered
+        /// </summary>
+        public long ReceiveDroppedFragments => IPDecoderReceive?.DroppedFragments ?? 0;
+
+        /// <summary>
+        /// Number of IP fragments of data sent from the local machine that were dropped because too many fragments were buffered
+        /// </summary>
+        public long SendDroppedFragments => IPDecoderSend?.DroppedFragments ?? 0;
+
         public override bool Equals(object obj)
         {
             return obj is TCPConnection c
@@ -62,6 +62,36 @@ public string WindowClass
         public bool UseRemoteIpFilter
         { get; set; } = true;
 
+        /// <summary>
+        /// Specifies the time in milliseconds to wait for missing TCP data before dropping buffered packets and resetting the stream.  Must be greater than zero.
+        /// </summary>
+        public int TCPGapTimeout
+        { get; set; } = 2000;
+
+        /// <summary>
+        /// Specifies the maximum number of TCP payload bytes to buffer per direction while waiting for missing data.  Must be greater than zero.
+        /// </summary>
+        public int TCPReassemblyWindow
+        { get; set; } = 1024 * 1024;
+
+        /// <summary>
+        /// Specifies the maximum number of IP fragments to buffer per direction while waiting for the rest of a fragmented packet.  Must be greater than zero.
+        /// </summary>
+        public int IPMaxFragments
+        { get; set; } = 100;
+
+        /// <summary>
+        /// Specifies the time in milliseconds to wait between reading captured data from the sockets.  Must be greater than zero.
+        /// </summary>
+        public int PollingInterval
+        { get; set; } = 30;
+
+        /// <summary>
+        /// Specifies the minimum time in milliseconds between refreshes of the process list and its TCP connections.  0 refreshes them every time data is polled.
+        /// </summary>
+        public int ConnectionRefreshInterval
+        { get; set; }
+
         public class RPCapConf
         {
             /// <summary>
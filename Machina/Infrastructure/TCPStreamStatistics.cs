@@ -0,0 +1,53 @@
+﻿// Copyright © 2021 Ravahn - All Rights Reserved
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY. without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see<http://www.gnu.org/licenses/>.
+
+namespace Machina.Infrastructure
+{
+    /// <summary>
+    /// Tracks data that was discarded by the TCP decoder while reassembling one direction of a TCP stream.
+    /// </summary>
+    public class TCPStreamStatistics
+    {
+        /// <summary>
+        /// Number of packets discarded because all of their data had already been processed, e.g. retransmits
+        /// </summary>
+        public long DuplicatePackets
+        { get; internal set; }
+
+        /// <summary>
+        /// Number of payload bytes discarded because they had already been processed, including overlapping portions of segments
+        /// </summary>
+        public long DuplicateBytes
+        { get; internal set; }
+
+        /// <summary>
+        /// Number of packets discarded when the stream was reset because of missing data
+        /// </summary>
+        public long DroppedPackets
+        { get; internal set; }
+
+        /// <summary>
+        /// Number of payload bytes discarded when the stream was reset because of missing data
+        /// </summary>
+        public long DroppedBytes
+        { get; internal set; }
+
+        /// <summary>
+        /// Number of times the stream was reset, either because the gap timeout expired or the reassembly window was exceeded
+        /// </summary>
+        public long StreamResets
+        { get; internal set; }
+    }
+}
@@ -36,12 +36,18 @@ namespace Machina
     ///       the primary interface for the process and capture all data sent/received on that interface - and filter it.  The new behavior may cause some data to be lost 
     ///       on connection startup, but significantly reduces the filtering overhead caused by other traffic on the network interface.
     ///     RPCap: specifies configuration properties to engage RPcap uri-based network parameters
+    ///     PollingInterval: time in milliseconds between reads of captured data
+    ///     ConnectionRefreshInterval: minimum time in milliseconds between lookups of the process and its TCP connections
+    ///     TCPGapTimeout: time in milliseconds to wait for missing TCP data before the buffered packets are dropped
+    ///     TCPReassemblyWindow: maximum number of TCP payload bytes to buffer per direction while waiting for missing data
+    ///     IPMaxFragments: maximum number of IP fragments to buffer per direction while waiting for the rest of a fragmented packet
     ///     
-    ///   In addition to the configuration properties, two public delegates are exposed
+    ///   In addition to the configuration properties, three public delegates are exposed
     ///     DataReceivedEventHandler: Delegate that is called when data is received and successfully decoded through IP and TCP decoders.  Note that a connection identifier is 
     ///       supplied to distinguish between multiple connections.
     ///     DataSentEventHandler: Delegate that is called when data is sent and successfully decoded through IP and TCP decoders.  Note that a connection identifier is 
     ///       supplied to distinguish between multiple connections.
+    ///     ConnectionClosedEventHandler: Delegate that is called when a connection is no longer found during a refresh, so that any state kept for it can be released.
     /// </summary>
     public class TCPNetworkMonitor : IDisposable
     {
@@ -71,6 +77,18 @@ public void OnDataSent(TCPConnection connection, byte[] data)
         {
             DataSentEventHandler?.Invoke(connection, data);
         }
+
+        public delegate void ConnectionClosedDelegate(TCPConnection connection);
+
+        /// <summary>
+        /// Specifies the delegate that is called when a connection is no longer found for the monitored processes and capture has stopped.
+        /// </summary>
+        public ConnectionClosedDelegate ConnectionClosedEventHandler;
+
+        public void OnConnectionClosed(TCPConnection connection)
+        {
+            ConnectionClosedEventHandler?.Invoke(connection);
+        }
         #endregion
 
         private readonly ConnectionManager _connectionManager = new ConnectionManager();
@@ -79,6 +97,7 @@ public void OnDataSent(TCPConnection connection, byte[] data)
 
         private bool _disposedValue;
         private DateTime _lastLoopError = DateTime.MinValue;
+        private DateTime _lastConnectionRefresh = DateTime.MinValue;
 
         /// <summary>
         /// Validates the parameters and starts the monitor.
@@ -89,6 +108,17 @@ public void Start()
                 throw new ArgumentException("TCPNetworkMonitor: Please specify one of Config.ProcessID, Config.ProcessIDList, Config.WindowName or Config.WindowClass.");
             if (DataReceivedEventHandler == null && DataSentEventHandler == null)
                 throw new ArgumentException("TCPNetworkMonitor: Please set DataReceivedEventHandler and/or DataSentEventHandler.");
+            if (Config.PollingInterval <= 0)
+                throw new ArgumentException("TCPNetworkMonitor: Config.PollingInterval must be greater than zero.");
+            if (Config.TCPGapTimeout <= 0)
+                throw new ArgumentException("TCPNetworkMonitor: Config.TCPGapTimeout must be greater than zero.");
+            if (Config.TCPReassemblyWindow <= 0)
+                throw new ArgumentException("TCPNetworkMonitor: Config.TCPReassemblyWindow must be greater than zero.");
+            if (Config.IPMaxFragments <= 0)
+                throw new ArgumentException("TCPNetworkMonitor: Config.IPMaxFragments must be greater than zero.");
+
+            _lastConnectionRefresh = DateTime.MinValue;
+            _connectionManager.ConnectionClosedEventHandler = (TCPConnection connection) => OnConnectionClosed(connection);
 
             _tokenSource = new CancellationTokenSource();
             _monitorTask = Task.Run(() => ProcessDataLoop(_tokenSource.Token));
@@ -124,7 +154,11 @@ private void ProcessDataLoop(CancellationToken token)
                 {
                     try
                     {
-                        _connectionManager.Refresh();
+                        if (DateTime.UtcNow.Subtract(_lastConnectionRefresh).TotalMilliseconds >= Config.ConnectionRefreshInterval)
+                        {
+                            _connectionManager.Refresh();
+                            _lastConnectionRefresh = DateTime.UtcNow;
+                        }
 
                         ProcessNetworkData();
 
@@ -140,7 +174,7 @@ private void ProcessDataLoop(CancellationToken token)
                         _lastLoopError = DateTime.UtcNow;
                     }
 
-                    Task.Delay(30, token).Wait(token);
+                    Task.Delay(Config.PollingInterval, token).Wait(token);
                 }
             }
             catch (OperationCanceledException)